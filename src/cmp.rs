@@ -0,0 +1,410 @@
+//! The eight comparison functions, plus [`sort_key`] for precomputing a comparable byte key.
+//!
+//! See the crate-level docs for how these functions differ from each other.
+
+use crate::iter::fold_char;
+use core::cmp::Ordering;
+
+/// A single logical "unit" read off a string: a character, tagged with whether the *original*,
+/// unfolded character was alphanumeric.
+type Unit = (bool, char);
+
+/// Walks a string's chars, folding and case-normalizing them, and optionally skipping
+/// non-alphanumeric characters, one [`Unit`] at a time.
+///
+/// This is the shared building block behind both the `*_cmp` functions and [`sort_key`]: both
+/// need to walk a string's folded, filtered characters the same way.
+struct Lookahead<'a> {
+    chars: core::str::Chars<'a>,
+    fold_buf: Option<char>,
+    pushed_back: Vec<Unit>,
+    lowercase: bool,
+    only_alnum: bool,
+}
+
+impl<'a> Lookahead<'a> {
+    fn new(s: &'a str, lowercase: bool, only_alnum: bool) -> Self {
+        Lookahead {
+            chars: s.chars(),
+            fold_buf: None,
+            pushed_back: Vec::new(),
+            lowercase,
+            only_alnum,
+        }
+    }
+
+    fn push_back(&mut self, unit: Unit) {
+        self.pushed_back.push(unit);
+    }
+
+    fn next(&mut self) -> Option<Unit> {
+        if let Some(unit) = self.pushed_back.pop() {
+            return Some(unit);
+        }
+        if let Some(c) = self.fold_buf.take() {
+            return Some((true, c));
+        }
+        loop {
+            let c = self.chars.next()?;
+            let alnum = c.is_alphanumeric();
+            if !alnum {
+                if self.only_alnum {
+                    continue;
+                }
+                return Some((false, c));
+            }
+            let mut folded = fold_char(c);
+            let first = folded.next().expect("folding always yields at least one char");
+            if let Some(second) = folded.next() {
+                self.fold_buf = Some(self.maybe_lower(second));
+            }
+            return Some((true, self.maybe_lower(first)));
+        }
+    }
+
+    /// Returns the next unit without consuming it.
+    fn peek(&mut self) -> Option<Unit> {
+        let unit = self.next()?;
+        self.push_back(unit);
+        Some(unit)
+    }
+
+    fn maybe_lower(&self, c: char) -> char {
+        if self.lowercase {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        }
+    }
+}
+
+/// A natural-mode number: an optional sign, an integer part (leading zeros stripped, but at
+/// least one digit kept) and an optional fractional part (following a `.`).
+struct Number {
+    negative: bool,
+    int_digits: Vec<char>,
+    frac_digits: Vec<char>,
+}
+
+/// Returns whether `unit` (already read off `la`, but not yet consumed further) looks like the
+/// start of a natural-mode number: a digit, or a `-` immediately followed by a digit.
+fn looks_like_number_start(la: &mut Lookahead, unit: Unit) -> bool {
+    let (alnum, c) = unit;
+    if alnum && c.is_ascii_digit() {
+        return true;
+    }
+    if !alnum && c == '-' {
+        if let Some((next_alnum, next_c)) = la.peek() {
+            return next_alnum && next_c.is_ascii_digit();
+        }
+    }
+    false
+}
+
+/// Consumes a run of consecutive ASCII digits (possibly empty).
+fn take_digit_run(la: &mut Lookahead) -> Vec<char> {
+    let mut digits = Vec::new();
+    loop {
+        match la.next() {
+            Some((true, c)) if c.is_ascii_digit() => digits.push(c),
+            Some(other) => {
+                la.push_back(other);
+                break;
+            }
+            None => break,
+        }
+    }
+    digits
+}
+
+fn strip_leading_zeros(digits: Vec<char>) -> Vec<char> {
+    let keep = digits.iter().position(|&c| c != '0').unwrap_or(digits.len().saturating_sub(1));
+    digits[keep..].to_vec()
+}
+
+/// Consumes a full number from `la`, given that `first` has already been confirmed (via
+/// [`looks_like_number_start`]) to start one.
+fn take_number(la: &mut Lookahead, first: Unit) -> Number {
+    let negative = !first.0 && first.1 == '-';
+    if !negative {
+        la.push_back(first);
+    }
+
+    let int_digits = strip_leading_zeros(take_digit_run(la));
+
+    let mut frac_digits = Vec::new();
+    if let Some((false, '.')) = la.peek() {
+        let dot = la.next().unwrap();
+        match la.peek() {
+            Some((true, d)) if d.is_ascii_digit() => frac_digits = take_digit_run(la),
+            _ => la.push_back(dot),
+        }
+    }
+
+    Number { negative, int_digits, frac_digits }
+}
+
+/// Compares two fractional-digit sequences as if both were right-padded with `0`s to the same
+/// length, so `5` (i.e. `.5`) and `50` (i.e. `.50`) compare equal.
+fn compare_fraction(a: &[char], b: &[char]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let da = a.get(i).copied().unwrap_or('0');
+        let db = b.get(i).copied().unwrap_or('0');
+        match da.cmp(&db) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    match (a.negative, b.negative) {
+        (false, true) => return Ordering::Greater,
+        (true, false) => return Ordering::Less,
+        _ => {}
+    }
+    let magnitude = match a.int_digits.len().cmp(&b.int_digits.len()) {
+        Ordering::Equal => a
+            .int_digits
+            .cmp(&b.int_digits)
+            .then_with(|| compare_fraction(&a.frac_digits, &b.frac_digits)),
+        other => other,
+    };
+    // Both negative (or both non-negative): for negative numbers a larger magnitude sorts
+    // first, e.g. `-100 < -50`.
+    if a.negative {
+        magnitude.reverse()
+    } else {
+        magnitude
+    }
+}
+
+fn compare(a: &str, b: &str, lexical: bool, natural: bool, only_alnum: bool) -> Ordering {
+    let mut la = Lookahead::new(a, lexical, only_alnum);
+    let mut lb = Lookahead::new(b, lexical, only_alnum);
+    loop {
+        match (la.next(), lb.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(first_a), Some(first_b)) => {
+                if natural
+                    && looks_like_number_start(&mut la, first_a)
+                    && looks_like_number_start(&mut lb, first_b)
+                {
+                    let na = take_number(&mut la, first_a);
+                    let nb = take_number(&mut lb, first_b);
+                    match compare_numbers(&na, &nb) {
+                        Ordering::Equal => continue,
+                        other => return other,
+                    }
+                }
+                if first_a.0 != first_b.0 {
+                    return first_a.0.cmp(&first_b.0);
+                }
+                match first_a.1.cmp(&first_b.1) {
+                    Ordering::Equal => continue,
+                    other => return other,
+                }
+            }
+        }
+    }
+}
+
+/// Compares two strings, without any special handling: non-ASCII characters are folded to their
+/// closest ASCII equivalent, but case and numbers are otherwise compared as-is.
+pub fn cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, false, false, false)
+}
+
+/// Like [`cmp`], but skips characters that aren't alphanumeric.
+pub fn only_alnum_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, false, false, true)
+}
+
+/// Like [`cmp`], but case-insensitive.
+pub fn lexical_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, true, false, false)
+}
+
+/// Like [`lexical_cmp`], but skips characters that aren't alphanumeric.
+pub fn lexical_only_alnum_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, true, false, true)
+}
+
+/// Like [`cmp`], but numbers are compared by their numeric value (`50` < `100`). A `-`
+/// immediately followed by a digit makes the number negative (`-100 < -50 < 0 < 50`), and a `.`
+/// followed by digits is compared as a fractional part (`1.25 < 1.5`).
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, false, true, false)
+}
+
+/// Like [`natural_cmp`], but skips characters that aren't alphanumeric.
+pub fn natural_only_alnum_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, false, true, true)
+}
+
+/// Like [`natural_cmp`], but case-insensitive.
+pub fn natural_lexical_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, true, true, false)
+}
+
+/// Like [`natural_lexical_cmp`], but skips characters that aren't alphanumeric.
+pub fn natural_lexical_only_alnum_cmp(a: &str, b: &str) -> Ordering {
+    compare(a, b, true, true, true)
+}
+
+/// Options controlling how [`sort_key`] encodes a string, mirroring the choice between the
+/// eight `*_cmp` functions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SortKeyOptions {
+    /// Case-insensitive, matching the `lexical` comparison functions.
+    pub lexical: bool,
+    /// Numbers are compared by their numeric value, matching the `natural` comparison
+    /// functions.
+    pub natural: bool,
+    /// Characters that aren't alphanumeric are skipped entirely, matching the `only_alnum`
+    /// comparison functions.
+    pub only_alnum: bool,
+}
+
+/// A byte that sorts below both tag bytes and never starts an alphanumeric character's UTF-8
+/// encoding, used to separate a string's encoded characters from its raw-bytes tiebreaker, and
+/// (after [`TAG_ALNUM`]) to mark the start of a natural-mode number.
+const SEPARATOR: u8 = 0x00;
+const TAG_NON_ALNUM: u8 = 0x01;
+const TAG_ALNUM: u8 = 0x02;
+
+fn digit_value(c: char) -> u8 {
+    c as u8 - b'0'
+}
+
+/// Encodes an integer digit run, complementing both the length and the digits when `negative` so
+/// that, compared as plain bytes, larger negative magnitudes sort first.
+///
+/// The length prefix is a full `u64` (rather than a single byte) so it can represent every digit
+/// run that can actually exist in memory, and every digit byte is emitted — no digits are ever
+/// dropped to fit the prefix, so integers of matching length still compare correctly past 255
+/// digits.
+fn encode_int_digits(digits: &[char], negative: bool) -> Vec<u8> {
+    let len = digits.len() as u64;
+    let len_bytes = if negative { u64::MAX - len } else { len }.to_be_bytes();
+    let mut out = Vec::with_capacity(len_bytes.len() + digits.len());
+    out.extend_from_slice(&len_bytes);
+    out.extend(digits.iter().map(|&c| {
+        let v = digit_value(c);
+        b'0' + if negative { 9 - v } else { v }
+    }));
+    out
+}
+
+/// Encodes a fractional digit run, with trailing `0`s stripped so that e.g. `5` and `50` (i.e.
+/// `.5` and `.50`) encode identically, complementing digits when `negative`.
+///
+/// For a non-negative number this needs no length prefix: every tag byte a number token's
+/// fractional digits can butt up against (the next token's, or the final tiebreaker's) is below
+/// `b'0'`, so a shorter (or empty) fractional run naturally sorts before a longer one that starts
+/// with the same digits, matching [`compare_fraction`]'s implicit-zero-padding semantics. For a
+/// negative number that relationship needs to be reversed (`-1.5 < -1`, the longer fraction sorts
+/// *first*), so a trailing byte higher than any digit byte is appended to make a shorter run sort
+/// after a longer one that starts with the same digits.
+fn encode_frac_digits(digits: &[char], negative: bool) -> Vec<u8> {
+    let trimmed = match digits.iter().rposition(|&c| c != '0') {
+        Some(last) => &digits[..=last],
+        None => &[],
+    };
+    let mut out: Vec<u8> = trimmed
+        .iter()
+        .map(|&c| {
+            let v = digit_value(c);
+            b'0' + if negative { 9 - v } else { v }
+        })
+        .collect();
+    if negative {
+        out.push(0xFF);
+    }
+    out
+}
+
+/// Encodes `s` into a byte buffer such that comparing two buffers with the standard `[u8]`
+/// ordering reproduces the result of the corresponding `*_cmp` function (chosen via `opts`).
+///
+/// This is useful for database-style indexing: rather than running the `O(n)` folding comparator
+/// on every comparison during a sort or a B-tree lookup, compute each key once with `sort_key`
+/// and compare the resulting bytes directly.
+///
+/// ```rust
+/// use lexicmp::{sort_key, SortKeyOptions, natural_lexical_cmp};
+///
+/// let opts = SortKeyOptions { lexical: true, natural: true, ..Default::default() };
+/// let mut keyed = vec!["img10.png", "img2.png", "img1.png"];
+/// keyed.sort_by(|a, b| sort_key(a, opts).cmp(&sort_key(b, opts)));
+///
+/// let mut compared = keyed.clone();
+/// compared.sort_by(|a, b| natural_lexical_cmp(a, b));
+/// assert_eq!(keyed, compared);
+/// ```
+pub fn sort_key(s: &str, opts: SortKeyOptions) -> Vec<u8> {
+    let mut key = Vec::with_capacity(s.len() + 1);
+    let mut la = Lookahead::new(s, opts.lexical, opts.only_alnum);
+    while let Some(unit) = la.next() {
+        if opts.natural && looks_like_number_start(&mut la, unit) {
+            let number = take_number(&mut la, unit);
+            if number.negative {
+                // `compare()` only groups a leading `-` into a number when the *other* side
+                // also looks like a number start; otherwise it falls back to comparing `-` as
+                // an ordinary non-alphanumeric character. Encode the sign that same way, so a
+                // negative number's key still compares correctly against a non-number key.
+                key.push(TAG_NON_ALNUM);
+                key.push(b'-');
+            }
+            key.push(TAG_ALNUM);
+            key.push(SEPARATOR);
+            key.extend(encode_int_digits(&number.int_digits, number.negative));
+            key.extend(encode_frac_digits(&number.frac_digits, number.negative));
+            continue;
+        }
+        key.push(if unit.0 { TAG_ALNUM } else { TAG_NON_ALNUM });
+        let mut buf = [0u8; 4];
+        key.extend(unit.1.encode_utf8(&mut buf).as_bytes());
+    }
+    key.push(SEPARATOR);
+    key.extend_from_slice(s.as_bytes());
+    key
+}
+
+#[test]
+fn test_sort_key_matches_natural_lexical_cmp() {
+    let opts = SortKeyOptions { lexical: true, natural: true, ..Default::default() };
+    let pairs = [
+        ("-100", "."),
+        ("-1.00000000000000000009", "-1.00000000000000000001"),
+        ("-100", "-50"),
+        ("-9", "-10"),
+        ("-1.5", "-1"),
+        ("-5.25", "-5.2500001"),
+        ("1.1", "1.10"),
+        ("0.1", "0.09"),
+        ("-100", "a"),
+        ("-100", "-"),
+        ("-0.5", "0"),
+        ("img10.png", "img2.png"),
+    ];
+    for (a, b) in pairs {
+        let expected = natural_lexical_cmp(a, b);
+        let actual = sort_key(a, opts).cmp(&sort_key(b, opts));
+        assert_eq!(actual, expected, "sort_key({a:?}) vs sort_key({b:?})");
+        let reverse_expected = natural_lexical_cmp(b, a);
+        let reverse_actual = sort_key(b, opts).cmp(&sort_key(a, opts));
+        assert_eq!(reverse_actual, reverse_expected, "sort_key({b:?}) vs sort_key({a:?})");
+    }
+}
+
+#[test]
+fn test_sort_key_int_digits_beyond_u8_len() {
+    let opts = SortKeyOptions { lexical: true, natural: true, ..Default::default() };
+    let a = format!("5{}", "0".repeat(255)); // 256 digits
+    let b = format!("1{}", "0".repeat(256)); // 257 digits
+    assert_eq!(sort_key(&a, opts).cmp(&sort_key(&b, opts)), natural_lexical_cmp(&a, &b));
+}
@@ -0,0 +1,99 @@
+//! Small combinators for composing the `*_cmp` functions (or any comparator with the same
+//! signature) without hand-writing closures for common patterns like tie-breaking.
+
+use core::cmp::Ordering;
+
+/// Runs `first`, falling back to `second` only when `first` returns [`Ordering::Equal`].
+///
+/// ## Example
+///
+/// ```rust
+/// use lexicmp::{combinators::then, natural_lexical_cmp, StringSort};
+///
+/// let mut words = vec!["bbb", "a", "cc", "dd"];
+/// words.string_sort(then(
+///     |a: &str, b: &str| a.len().cmp(&b.len()),
+///     natural_lexical_cmp,
+/// ));
+///
+/// assert_eq!(words, vec!["a", "cc", "dd", "bbb"]);
+/// ```
+pub fn then<A, B>(mut first: A, mut second: B) -> impl FnMut(&str, &str) -> Ordering
+where
+    A: FnMut(&str, &str) -> Ordering,
+    B: FnMut(&str, &str) -> Ordering,
+{
+    move |a, b| match first(a, b) {
+        Ordering::Equal => second(a, b),
+        other => other,
+    }
+}
+
+/// Reverses the order produced by `cmp`.
+///
+/// ## Example
+///
+/// ```rust
+/// use lexicmp::{combinators::reverse, natural_lexical_cmp, StringSort};
+///
+/// let mut words = vec!["a", "cc", "dd", "bbb"];
+/// words.string_sort(reverse(natural_lexical_cmp));
+///
+/// assert_eq!(words, vec!["dd", "cc", "bbb", "a"]);
+/// ```
+pub fn reverse<Cmp>(mut cmp: Cmp) -> impl FnMut(&str, &str) -> Ordering
+where
+    Cmp: FnMut(&str, &str) -> Ordering,
+{
+    move |a, b| cmp(a, b).reverse()
+}
+
+/// Adapts `cmp` to compare `a` and `b` by a derived key, e.g. to sort by descending length:
+/// `by_key(|s| s.len(), ...)` combined with [`reverse`].
+///
+/// ## Example
+///
+/// Sort by descending string length, breaking ties with case-insensitive lexical order:
+///
+/// ```rust
+/// use lexicmp::{combinators::{by_key, reverse, then}, natural_lexical_cmp, StringSort};
+///
+/// let mut words = vec!["bb", "a", "cc", "ddd"];
+/// words.string_sort(then(reverse(by_key(str::len, usize::cmp)), natural_lexical_cmp));
+///
+/// assert_eq!(words, vec!["ddd", "bb", "cc", "a"]);
+/// ```
+pub fn by_key<Key, K, Cmp>(mut key: Key, mut cmp: Cmp) -> impl FnMut(&str, &str) -> Ordering
+where
+    Key: FnMut(&str) -> K,
+    Cmp: FnMut(&K, &K) -> Ordering,
+{
+    move |a, b| cmp(&key(a), &key(b))
+}
+
+#[test]
+fn test_then() {
+    use crate::StringSort;
+
+    let mut words = vec!["bbb", "a", "cc", "dd"];
+    words.string_sort(then(|a: &str, b: &str| a.len().cmp(&b.len()), |a: &str, b: &str| a.cmp(b)));
+    assert_eq!(words, vec!["a", "cc", "dd", "bbb"]);
+}
+
+#[test]
+fn test_reverse() {
+    use crate::StringSort;
+
+    let mut words = vec!["a", "cc", "dd", "bbb"];
+    words.string_sort(reverse(|a: &str, b: &str| a.len().cmp(&b.len())));
+    assert_eq!(words, vec!["bbb", "cc", "dd", "a"]);
+}
+
+#[test]
+fn test_by_key() {
+    use crate::StringSort;
+
+    let mut words = vec!["bb", "a", "ccc"];
+    words.string_sort(by_key(str::len, usize::cmp));
+    assert_eq!(words, vec!["a", "bb", "ccc"]);
+}
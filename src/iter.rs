@@ -0,0 +1,66 @@
+//! Low-level iterator helpers used by [`crate::cmp`].
+//!
+//! These are kept separate from `cmp` (and `pub` rather than `pub(crate)`) so that callers who
+//! want to build their own comparator on top of this crate's ASCII-folding behaviour don't have
+//! to reimplement it.
+
+/// The ASCII folding of a single `char`, as one or two output chars, yielded in order.
+///
+/// Most characters fold to exactly themselves. A handful of accented Latin letters fold to their
+/// unaccented counterpart (`á` -> `a`), and a couple of ligature-like letters fold to two chars
+/// (`ß` -> `ss`, `æ` -> `ae`).
+pub struct Folded {
+    chars: [char; 2],
+    len: u8,
+    pos: u8,
+}
+
+impl Folded {
+    fn one(a: char) -> Self {
+        Folded { chars: [a, '\0'], len: 1, pos: 0 }
+    }
+
+    fn two(a: char, b: char) -> Self {
+        Folded { chars: [a, b], len: 2, pos: 0 }
+    }
+}
+
+impl Iterator for Folded {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let c = self.chars[self.pos as usize];
+        self.pos += 1;
+        Some(c)
+    }
+}
+
+/// Folds `c` to its closest ASCII representation, preserving its case.
+///
+/// Characters without a known ASCII folding are returned unchanged.
+pub fn fold_char(c: char) -> Folded {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let folded: &str = match lower {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ý' | 'ÿ' => "y",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return Folded::one(c),
+    };
+    let mut chars = folded.chars().map(|f| if c.is_uppercase() { f.to_ascii_uppercase() } else { f });
+    let a = chars.next().expect("fold table entries are non-empty");
+    match chars.next() {
+        Some(b) => Folded::two(a, b),
+        None => Folded::one(a),
+    }
+}
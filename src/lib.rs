@@ -6,8 +6,9 @@
 //! characters (punctuation, whitespace, special characters, emojis, ...).
 //!
 //! It is possible to enable **natural sorting**, which also handles ASCII numbers. For example,
-//! `50` is less than `100` with natural sorting turned on. It's also possible to skip
-//! characters that aren't alphanumeric, so e.g. `f-5` is next to `f5`.
+//! `50` is less than `100` with natural sorting turned on. Numbers can also be negative
+//! (`-100 < -50 < 0`) or decimal (`1.25 < 1.5`). It's also possible to skip characters that
+//! aren't alphanumeric, so e.g. `f-5` is next to `f5`.
 //!
 //! If different strings have the same ASCII representation (e.g. `"Foo"` and `"fóò"`), it
 //! falls back to the default method from the standard library, so sorting is deterministic.
@@ -46,14 +47,18 @@
 //! Note that only the functions that sort lexicographically are case insensitive.
 
 mod cmp;
+pub mod combinators;
 pub mod iter;
 
 pub use cmp::{
     cmp, lexical_cmp, lexical_only_alnum_cmp, natural_cmp, natural_lexical_cmp,
-    natural_lexical_only_alnum_cmp, natural_only_alnum_cmp, only_alnum_cmp,
+    natural_lexical_only_alnum_cmp, natural_only_alnum_cmp, only_alnum_cmp, sort_key,
+    SortKeyOptions,
 };
 
 use core::cmp::Ordering;
+use std::borrow::Cow;
+use std::path::Path;
 
 /// A trait to sort strings. This is a convenient wrapper for the standard library sort functions.
 ///
@@ -154,6 +159,23 @@ pub trait StringSort {
     where
         Cmp: FnMut(&str, &str) -> Ordering,
         Map: FnMut(&str) -> &str;
+
+    /// Sorts the items by their [`sort_key`], rather than by repeatedly running a `*_cmp`
+    /// function. The key for each item is computed once and cached for the duration of the
+    /// sort, which is worthwhile when `opts` matches a comparator you'd otherwise call `O(n log
+    /// n)` times, e.g. when building a sorted index.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use lexicmp::{SortKeyOptions, StringSort};
+    ///
+    /// let slice = &mut ["img10.png", "img2.png", "img1.png"];
+    /// slice.string_sort_by_key(SortKeyOptions { lexical: true, natural: true, ..Default::default() });
+    ///
+    /// assert_eq!(slice, &["img1.png", "img2.png", "img10.png"]);
+    /// ```
+    fn string_sort_by_key(&mut self, opts: SortKeyOptions);
 }
 
 impl<A: AsRef<str>> StringSort for [A] {
@@ -180,6 +202,109 @@ impl<A: AsRef<str>> StringSort for [A] {
     {
         self.sort_unstable_by(|lhs, rhs| cmp(map(lhs.as_ref()), map(rhs.as_ref())));
     }
+
+    fn string_sort_by_key(&mut self, opts: SortKeyOptions) {
+        self.sort_by_cached_key(|item| sort_key(item.as_ref(), opts));
+    }
+}
+
+/// A trait to sort file paths (or anything else that behaves like one, such as `OsStr`). This is
+/// a convenient wrapper for the standard library sort functions.
+///
+/// This trait is implemented for all slices whose inner type implements `AsRef<Path>`.
+///
+/// Paths are compared by their lossy UTF-8 representation (`Path::to_string_lossy`), so
+/// non-UTF-8 paths still sort deterministically, just not necessarily in the order a shell
+/// would produce for them.
+///
+/// ## Example
+///
+/// ```rust
+/// use lexicmp::PathSort;
+/// use std::path::{Path, PathBuf};
+///
+/// let mut paths: Vec<PathBuf> = ["img10.png", "img2.png", "img1.png"]
+///     .iter()
+///     .map(PathBuf::from)
+///     .collect();
+/// paths.path_sort_unstable(lexicmp::natural_lexical_cmp);
+///
+/// assert_eq!(
+///     paths,
+///     vec![Path::new("img1.png"), Path::new("img2.png"), Path::new("img10.png")]
+/// );
+/// ```
+pub trait PathSort {
+    /// Sorts the items using the provided comparison function.
+    ///
+    /// **This is a stable sort, which is often not required**.
+    /// You can use `path_sort_unstable` instead.
+    fn path_sort(&mut self, cmp: impl FnMut(&str, &str) -> Ordering);
+
+    /// Sorts the items using the provided comparison function.
+    ///
+    /// This sort is unstable: The original order of equal paths is not preserved.
+    /// It is slightly more efficient than the stable alternative.
+    fn path_sort_unstable(&mut self, cmp: impl FnMut(&str, &str) -> Ordering);
+
+    /// Sorts the items using the provided comparison function and another function that is
+    /// applied to each path's lossy UTF-8 representation before the comparison. This can be
+    /// used to trim the paths, or to compare by e.g. just the file name.
+    ///
+    /// If you do anything more complicated than trimming, you'll likely run into lifetime
+    /// problems. In this case you should use `[_]::sort_by()` directly.
+    ///
+    /// **This is a stable sort, which is often not required**.
+    /// You can use `path_sort_unstable_by` instead.
+    fn path_sort_by<Cmp, Map>(&mut self, cmp: Cmp, map: Map)
+    where
+        Cmp: FnMut(&str, &str) -> Ordering,
+        Map: FnMut(&str) -> &str;
+
+    /// Sorts the items using the provided comparison function and another function that is
+    /// applied to each path's lossy UTF-8 representation before the comparison. This can be
+    /// used to trim the paths, or to compare by e.g. just the file name.
+    ///
+    /// If you do anything more complicated than trimming, you'll likely run into lifetime
+    /// problems. In this case you should use `[_]::sort_by()` directly.
+    ///
+    /// This sort is unstable: The original order of equal paths is not preserved.
+    /// It is slightly more efficient than the stable alternative.
+    fn path_sort_unstable_by<Cmp, Map>(&mut self, cmp: Cmp, map: Map)
+    where
+        Cmp: FnMut(&str, &str) -> Ordering,
+        Map: FnMut(&str) -> &str;
+}
+
+impl<A: AsRef<Path>> PathSort for [A] {
+    fn path_sort(&mut self, mut cmp: impl FnMut(&str, &str) -> Ordering) {
+        self.sort_by(|lhs, rhs| cmp(&lossy(lhs.as_ref()), &lossy(rhs.as_ref())));
+    }
+
+    fn path_sort_unstable(&mut self, mut cmp: impl FnMut(&str, &str) -> Ordering) {
+        self.sort_unstable_by(|lhs, rhs| cmp(&lossy(lhs.as_ref()), &lossy(rhs.as_ref())));
+    }
+
+    fn path_sort_by<Cmp, Map>(&mut self, mut cmp: Cmp, mut map: Map)
+    where
+        Cmp: FnMut(&str, &str) -> Ordering,
+        Map: FnMut(&str) -> &str,
+    {
+        self.sort_by(|lhs, rhs| cmp(map(&lossy(lhs.as_ref())), map(&lossy(rhs.as_ref()))));
+    }
+
+    fn path_sort_unstable_by<Cmp, Map>(&mut self, mut cmp: Cmp, mut map: Map)
+    where
+        Cmp: FnMut(&str, &str) -> Ordering,
+        Map: FnMut(&str) -> &str,
+    {
+        self.sort_unstable_by(|lhs, rhs| cmp(map(&lossy(lhs.as_ref())), map(&lossy(rhs.as_ref()))));
+    }
+}
+
+/// Returns a path's lossy UTF-8 representation, borrowing when possible.
+fn lossy(path: &Path) -> Cow<'_, str> {
+    path.to_string_lossy()
 }
 
 #[test]
@@ -207,3 +332,29 @@ fn test_sort() {
     assert_lexically_sorted!(string_sort, strings, natural = false);
     assert_lexically_sorted!(string_sort, strings_nat, natural = true);
 }
+
+#[test]
+fn test_path_sort() {
+    use std::path::PathBuf;
+
+    let mut paths: Vec<PathBuf> =
+        ["img10.png", "img2.png", "img1.png"].iter().map(PathBuf::from).collect();
+    paths.path_sort_unstable(natural_lexical_cmp);
+    assert_eq!(
+        paths,
+        ["img1.png", "img2.png", "img10.png"].iter().map(PathBuf::from).collect::<Vec<_>>()
+    );
+
+    let mut paths: Vec<PathBuf> =
+        ["dir/b", "dir/a", "dir/c"].iter().map(PathBuf::from).collect();
+    paths.path_sort_by(natural_lexical_cmp, |p| p.rsplit('/').next().unwrap_or(p));
+    assert_eq!(paths, ["dir/a", "dir/b", "dir/c"].iter().map(PathBuf::from).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_string_sort_by_key() {
+    let opts = SortKeyOptions { lexical: true, natural: true, ..Default::default() };
+    let mut strings = vec!["img10.png", "img2.png", "img1.png"];
+    strings.string_sort_by_key(opts);
+    assert_eq!(strings, vec!["img1.png", "img2.png", "img10.png"]);
+}